@@ -0,0 +1,33 @@
+pub mod rect;
+pub mod render;
+pub mod texture_atlas;
+
+pub use rect::Rect;
+
+use bevy_asset::HandleUntyped;
+use bevy_ecs::component::Component;
+use bevy_math::Vec2;
+use bevy_render2::{color::Color, render_resource::Shader};
+use render::BlendMode;
+
+pub const SPRITE_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 9016885805180281612);
+
+/// A 2D sprite, drawn from a single texture.
+#[derive(Debug, Clone, Component)]
+pub struct Sprite {
+    pub custom_size: Option<Vec2>,
+    pub color: Color,
+    /// How this sprite's texels are combined with what's already in the render target.
+    pub blend_mode: BlendMode,
+}
+
+impl Default for Sprite {
+    fn default() -> Self {
+        Self {
+            custom_size: None,
+            color: Color::WHITE,
+            blend_mode: Default::default(),
+        }
+    }
+}
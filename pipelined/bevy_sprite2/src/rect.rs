@@ -0,0 +1,17 @@
+use bevy_math::Vec2;
+
+/// A rectangle defined by its minimum and maximum points.
+#[derive(Default, Clone, Copy, Debug)]
+pub struct Rect {
+    /// The beginning point of the rect
+    pub min: Vec2,
+    /// The ending point of the rect
+    pub max: Vec2,
+}
+
+impl Rect {
+    /// The width and height of the rect.
+    pub fn size(&self) -> Vec2 {
+        self.max - self.min
+    }
+}
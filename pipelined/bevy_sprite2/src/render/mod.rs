@@ -10,29 +10,66 @@ use bevy_ecs::{
 };
 use bevy_math::{Mat4, Vec2, Vec3, Vec4Swizzles};
 use bevy_render2::{
+    color::Color,
     mesh::{shape::Quad, Indices, Mesh, VertexAttributeValues},
     render_asset::RenderAssets,
     render_phase::{Draw, DrawFunctions, RenderPhase, TrackedRenderPass},
     render_resource::*,
     renderer::{RenderDevice, RenderQueue},
     texture::{BevyDefault, Image},
-    view::{ViewUniformOffset, ViewUniforms},
+    view::{ExtractedView, ViewUniformOffset, ViewUniforms},
 };
+use bevy_tasks::ComputeTaskPool;
 use bevy_transform::components::GlobalTransform;
 use bevy_utils::HashMap;
 use bytemuck::{Pod, Zeroable};
+use std::ops::Range;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum BlendMode {
+    Alpha,
+    Additive,
+    Multiply,
+    // Uses an alpha cutoff in the shader (`COLOR_MASK`) instead of a blend state.
+    Opaque,
+}
+
+impl Default for BlendMode {
+    fn default() -> Self {
+        BlendMode::Alpha
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SpriteKey {
+    pub blend_mode: BlendMode,
+}
+
+/// Selects which of `SpritePipeline`'s two cached samplers a sprite is bound with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Component)]
+pub enum ImageSampling {
+    Nearest,
+    Linear,
+}
+
+impl Default for ImageSampling {
+    fn default() -> Self {
+        ImageSampling::Linear
+    }
+}
 
 pub struct SpritePipeline {
     view_layout: BindGroupLayout,
     material_layout: BindGroupLayout,
-    pipeline: CachedPipelineId,
+    nearest_sampler: Sampler,
+    linear_sampler: Sampler,
+    specialized_pipelines: HashMap<SpriteKey, CachedPipelineId>,
 }
 
 impl FromWorld for SpritePipeline {
     fn from_world(world: &mut World) -> Self {
         let world = world.cell();
         let render_device = world.get_resource::<RenderDevice>().unwrap();
-        let mut pipeline_cache = world.get_resource_mut::<RenderPipelineCache>().unwrap();
 
         let view_layout = render_device.create_bind_group_layout(&BindGroupLayoutDescriptor {
             entries: &[BindGroupLayoutEntry {
@@ -57,7 +94,10 @@ impl FromWorld for SpritePipeline {
                     visibility: ShaderStages::FRAGMENT,
                     ty: BindingType::Texture {
                         multisampled: false,
-                        sample_type: TextureSampleType::Float { filterable: false },
+                        // Must agree with the `filtering: true` sampler entry below: a
+                        // non-filterable texture can only be paired with a non-filtering
+                        // sampler, which would reject the `ImageSampling::Linear` sampler.
+                        sample_type: TextureSampleType::Float { filterable: true },
                         view_dimension: TextureViewDimension::D2,
                     },
                     count: None,
@@ -75,35 +115,62 @@ impl FromWorld for SpritePipeline {
             label: Some("sprite_material_layout"),
         });
 
-        let descriptor = RenderPipelineDescriptor {
-            vertex: VertexState {
-                shader: SPRITE_SHADER_HANDLE.typed::<Shader>(),
-                entry_point: "vertex".into(),
-                shader_defs: vec![],
-                buffers: vec![VertexBufferLayout {
-                    array_stride: 20,
-                    step_mode: VertexStepMode::Vertex,
-                    attributes: vec![
-                        VertexAttribute {
-                            format: VertexFormat::Float32x3,
-                            offset: 0,
-                            shader_location: 0,
-                        },
-                        VertexAttribute {
-                            format: VertexFormat::Float32x2,
-                            offset: 12,
-                            shader_location: 1,
-                        },
-                    ],
-                }],
-            },
-            fragment: Some(FragmentState {
-                shader: SPRITE_SHADER_HANDLE.typed::<Shader>(),
-                shader_defs: vec![],
-                entry_point: "fragment".into(),
-                targets: vec![ColorTargetState {
-                    format: TextureFormat::bevy_default(),
-                    blend: Some(BlendState {
+        let nearest_sampler = render_device.create_sampler(&SamplerDescriptor {
+            label: Some("sprite_nearest_sampler"),
+            mag_filter: FilterMode::Nearest,
+            min_filter: FilterMode::Nearest,
+            mipmap_filter: FilterMode::Nearest,
+            ..Default::default()
+        });
+
+        let linear_sampler = render_device.create_sampler(&SamplerDescriptor {
+            label: Some("sprite_linear_sampler"),
+            mag_filter: FilterMode::Linear,
+            min_filter: FilterMode::Linear,
+            mipmap_filter: FilterMode::Linear,
+            ..Default::default()
+        });
+
+        SpritePipeline {
+            view_layout,
+            material_layout,
+            nearest_sampler,
+            linear_sampler,
+            specialized_pipelines: HashMap::default(),
+        }
+    }
+}
+
+impl SpritePipeline {
+    fn sampler(&self, sampling: ImageSampling) -> &Sampler {
+        match sampling {
+            ImageSampling::Nearest => &self.nearest_sampler,
+            ImageSampling::Linear => &self.linear_sampler,
+        }
+    }
+}
+
+impl SpritePipeline {
+    /// Returns the `CachedPipelineId` for `key`, queuing a new specialized pipeline the first
+    /// time a given key is seen.
+    fn specialize(
+        &mut self,
+        pipeline_cache: &mut RenderPipelineCache,
+        key: SpriteKey,
+    ) -> CachedPipelineId {
+        let view_layout = self.view_layout.clone();
+        let material_layout = self.material_layout.clone();
+        *self
+            .specialized_pipelines
+            .entry(key)
+            .or_insert_with(|| {
+                let mut shader_defs = Vec::new();
+                if key.blend_mode == BlendMode::Opaque {
+                    shader_defs.push("COLOR_MASK".to_string());
+                }
+
+                let blend = match key.blend_mode {
+                    BlendMode::Alpha => Some(BlendState {
                         color: BlendComponent {
                             src_factor: BlendFactor::SrcAlpha,
                             dst_factor: BlendFactor::OneMinusSrcAlpha,
@@ -115,33 +182,91 @@ impl FromWorld for SpritePipeline {
                             operation: BlendOperation::Add,
                         },
                     }),
-                    write_mask: ColorWrites::ALL,
-                }],
-            }),
-            layout: Some(vec![view_layout.clone(), material_layout.clone()]),
-            primitive: PrimitiveState {
-                front_face: FrontFace::Ccw,
-                cull_mode: None,
-                polygon_mode: PolygonMode::Fill,
-                clamp_depth: false,
-                conservative: false,
-                topology: PrimitiveTopology::TriangleList,
-                strip_index_format: None,
-            },
-            depth_stencil: None,
-            multisample: MultisampleState {
-                count: 1,
-                mask: !0,
-                alpha_to_coverage_enabled: false,
-            },
-            label: Some("sprite_pipeline".into()),
-        };
+                    BlendMode::Additive => Some(BlendState {
+                        color: BlendComponent {
+                            src_factor: BlendFactor::SrcAlpha,
+                            dst_factor: BlendFactor::One,
+                            operation: BlendOperation::Add,
+                        },
+                        alpha: BlendComponent {
+                            src_factor: BlendFactor::Zero,
+                            dst_factor: BlendFactor::One,
+                            operation: BlendOperation::Add,
+                        },
+                    }),
+                    BlendMode::Multiply => Some(BlendState {
+                        color: BlendComponent {
+                            src_factor: BlendFactor::Dst,
+                            dst_factor: BlendFactor::Zero,
+                            operation: BlendOperation::Add,
+                        },
+                        alpha: BlendComponent {
+                            src_factor: BlendFactor::DstAlpha,
+                            dst_factor: BlendFactor::Zero,
+                            operation: BlendOperation::Add,
+                        },
+                    }),
+                    BlendMode::Opaque => None,
+                };
 
-        SpritePipeline {
-            pipeline: pipeline_cache.queue(descriptor),
-            view_layout,
-            material_layout,
-        }
+                let descriptor = RenderPipelineDescriptor {
+                    vertex: VertexState {
+                        shader: SPRITE_SHADER_HANDLE.typed::<Shader>(),
+                        entry_point: "vertex".into(),
+                        shader_defs: shader_defs.clone(),
+                        buffers: vec![VertexBufferLayout {
+                            array_stride: 36,
+                            step_mode: VertexStepMode::Vertex,
+                            attributes: vec![
+                                VertexAttribute {
+                                    format: VertexFormat::Float32x3,
+                                    offset: 0,
+                                    shader_location: 0,
+                                },
+                                VertexAttribute {
+                                    format: VertexFormat::Float32x2,
+                                    offset: 12,
+                                    shader_location: 1,
+                                },
+                                VertexAttribute {
+                                    format: VertexFormat::Float32x4,
+                                    offset: 20,
+                                    shader_location: 2,
+                                },
+                            ],
+                        }],
+                    },
+                    fragment: Some(FragmentState {
+                        shader: SPRITE_SHADER_HANDLE.typed::<Shader>(),
+                        shader_defs,
+                        entry_point: "fragment".into(),
+                        targets: vec![ColorTargetState {
+                            format: TextureFormat::bevy_default(),
+                            blend,
+                            write_mask: ColorWrites::ALL,
+                        }],
+                    }),
+                    layout: Some(vec![view_layout.clone(), material_layout.clone()]),
+                    primitive: PrimitiveState {
+                        front_face: FrontFace::Ccw,
+                        cull_mode: None,
+                        polygon_mode: PolygonMode::Fill,
+                        clamp_depth: false,
+                        conservative: false,
+                        topology: PrimitiveTopology::TriangleList,
+                        strip_index_format: None,
+                    },
+                    depth_stencil: None,
+                    multisample: MultisampleState {
+                        count: 1,
+                        mask: !0,
+                        alpha_to_coverage_enabled: false,
+                    },
+                    label: Some("sprite_pipeline".into()),
+                };
+
+                pipeline_cache.queue(descriptor)
+            })
     }
 }
 
@@ -150,7 +275,73 @@ pub struct ExtractedSprite {
     rect: Rect,
     handle: Handle<Image>,
     atlas_size: Option<Vec2>,
-    vertex_index: usize,
+    blend_mode: BlendMode,
+    color: Color,
+    image_sampling: ImageSampling,
+    // Set by `cull_sprites`, which runs before `prepare_sprites`. Defaults to `true`.
+    visible: bool,
+}
+
+impl ExtractedSprite {
+    // Local corners are -extent..extent, matching `SpriteMeta`'s origin-centered `Quad` mesh.
+    fn world_aabb(&self) -> Aabb2d {
+        let extent = self.rect.size() * 0.5;
+        let corners = [
+            Vec2::new(-extent.x, -extent.y),
+            Vec2::new(extent.x, -extent.y),
+            Vec2::new(-extent.x, extent.y),
+            Vec2::new(extent.x, extent.y),
+        ];
+
+        let mut min = Vec2::splat(f32::MAX);
+        let mut max = Vec2::splat(f32::MIN);
+        for corner in corners {
+            let world = (self.transform * corner.extend(0.0).extend(1.0)).xyz().xy();
+            min = min.min(world);
+            max = max.max(world);
+        }
+
+        Aabb2d { min, max }
+    }
+}
+
+#[derive(Clone, Copy)]
+struct Aabb2d {
+    min: Vec2,
+    max: Vec2,
+}
+
+impl Aabb2d {
+    fn intersects(&self, other: &Aabb2d) -> bool {
+        self.min.x <= other.max.x
+            && self.max.x >= other.min.x
+            && self.min.y <= other.max.y
+            && self.max.y >= other.min.y
+    }
+
+    // Reconstructed from the projection rather than the viewport's pixel size, so zoomed 2D
+    // cameras (OrthographicProjection::scale != 1.0) are culled against the right rectangle.
+    fn from_view(view: &ExtractedView) -> Aabb2d {
+        let view_from_clip = view.projection.inverse();
+        let world_from_view = view.transform.compute_matrix();
+        let ndc_corners = [
+            Vec2::new(-1.0, -1.0),
+            Vec2::new(1.0, -1.0),
+            Vec2::new(-1.0, 1.0),
+            Vec2::new(1.0, 1.0),
+        ];
+
+        let mut min = Vec2::splat(f32::MAX);
+        let mut max = Vec2::splat(f32::MIN);
+        for corner in ndc_corners {
+            let view_space = view_from_clip.transform_point3(corner.extend(0.0));
+            let world = world_from_view.transform_point3(view_space).xy();
+            min = min.min(world);
+            max = max.max(world);
+        }
+
+        Aabb2d { min, max }
+    }
 }
 
 pub fn extract_atlases(
@@ -161,10 +352,13 @@ pub fn extract_atlases(
         &TextureAtlasSprite,
         &GlobalTransform,
         &Handle<TextureAtlas>,
+        Option<&ImageSampling>,
     )>,
 ) {
     let mut sprites = Vec::new();
-    for (entity, atlas_sprite, transform, texture_atlas_handle) in atlas_query.iter() {
+    for (entity, atlas_sprite, transform, texture_atlas_handle, image_sampling) in
+        atlas_query.iter()
+    {
         if let Some(texture_atlas) = texture_atlases.get(texture_atlas_handle) {
             let rect = texture_atlas.textures[atlas_sprite.index as usize];
             sprites.push((
@@ -174,7 +368,10 @@ pub fn extract_atlases(
                     transform: transform.compute_matrix(),
                     rect,
                     handle: texture_atlas.texture.clone_weak(),
-                    vertex_index: 0,
+                    blend_mode: atlas_sprite.blend_mode,
+                    color: atlas_sprite.color,
+                    image_sampling: image_sampling.copied().unwrap_or_default(),
+                    visible: true,
                 },),
             ));
         }
@@ -185,10 +382,16 @@ pub fn extract_atlases(
 pub fn extract_sprites(
     mut commands: Commands,
     images: Res<Assets<Image>>,
-    sprite_query: Query<(Entity, &Sprite, &GlobalTransform, &Handle<Image>)>,
+    sprite_query: Query<(
+        Entity,
+        &Sprite,
+        &GlobalTransform,
+        &Handle<Image>,
+        Option<&ImageSampling>,
+    )>,
 ) {
     let mut sprites = Vec::new();
-    for (entity, sprite, transform, handle) in sprite_query.iter() {
+    for (entity, sprite, transform, handle, image_sampling) in sprite_query.iter() {
         if let Some(image) = images.get(handle) {
             let size = image.texture_descriptor.size;
 
@@ -204,7 +407,10 @@ pub fn extract_sprites(
                             .unwrap_or_else(|| Vec2::new(size.width as f32, size.height as f32)),
                     },
                     handle: handle.clone_weak(),
-                    vertex_index: 0,
+                    blend_mode: sprite.blend_mode,
+                    color: sprite.color,
+                    image_sampling: image_sampling.copied().unwrap_or_default(),
+                    visible: true,
                 },),
             ));
         };
@@ -212,11 +418,33 @@ pub fn extract_sprites(
     commands.insert_or_spawn_batch(sprites);
 }
 
+// `visible` is OR'd across all views, so with more than one active camera (split-screen, a
+// minimap) a sprite visible to only one of them is still drawn into every other camera's phase.
+pub fn cull_sprites(
+    task_pool: Res<ComputeTaskPool>,
+    views: Query<&ExtractedView>,
+    mut extracted_sprites: Query<&mut ExtractedSprite>,
+) {
+    let view_rects: Vec<Aabb2d> = views.iter().map(Aabb2d::from_view).collect();
+
+    // No views means nothing will be drawn this frame; leave sprites as extracted rather than
+    // culling everything on an empty view list.
+    if view_rects.is_empty() {
+        return;
+    }
+
+    extracted_sprites.par_for_each_mut(&task_pool, 64, |mut extracted_sprite| {
+        let aabb = extracted_sprite.world_aabb();
+        extracted_sprite.visible = view_rects.iter().any(|view_rect| aabb.intersects(view_rect));
+    });
+}
+
 #[repr(C)]
 #[derive(Copy, Clone, Pod, Zeroable)]
 struct SpriteVertex {
     pub position: [f32; 3],
     pub uv: [f32; 2],
+    pub color: [f32; 4],
 }
 
 pub struct SpriteMeta {
@@ -241,17 +469,45 @@ impl Default for SpriteMeta {
     }
 }
 
+/// A contiguous run of sprites in `SpriteMeta` that share a material bind group and can be
+/// drawn with a single `draw_indexed` call.
+pub struct SpriteBatch {
+    range: Range<u32>,
+    handle: Handle<Image>,
+    blend_mode: BlendMode,
+    image_sampling: ImageSampling,
+}
+
 pub fn prepare_sprites(
+    mut commands: Commands,
     render_device: Res<RenderDevice>,
     render_queue: Res<RenderQueue>,
     mut sprite_meta: ResMut<SpriteMeta>,
-    mut extracted_sprites: Query<&mut ExtractedSprite>,
+    extracted_sprites: Query<(Entity, &ExtractedSprite)>,
 ) {
-    let extracted_sprite_len = extracted_sprites.iter_mut().len();
+    // Sort sprites by texture handle, blend mode and sampling so that sprites sharing a material
+    // bind group and pipeline end up contiguous in the vertex/index buffers and can be collapsed
+    // into a single batch below.
+    // Sprites `cull_sprites` determined are off-screen are dropped here, before any vertex/index
+    // data is generated for them.
+    let mut extracted_sprites: Vec<_> = extracted_sprites
+        .iter()
+        .filter(|(_, sprite)| sprite.visible)
+        .collect();
     // dont create buffers when there are no sprites
-    if extracted_sprite_len == 0 {
+    if extracted_sprites.is_empty() {
         return;
     }
+    // Batches below are merged by (handle, blend_mode, image_sampling), so the sort key must
+    // match: sorting on the handle alone can interleave sprites that share a texture but differ
+    // in blend mode or sampling, splitting what should be one contiguous batch into several.
+    extracted_sprites.sort_unstable_by_key(|(_, sprite)| {
+        (
+            sprite.handle.clone_weak(),
+            sprite.blend_mode,
+            sprite.image_sampling,
+        )
+    });
 
     let quad_vertex_positions = if let VertexAttributeValues::Float32x3(vertex_positions) =
         sprite_meta
@@ -272,14 +528,17 @@ pub fn prepare_sprites(
     };
 
     sprite_meta.vertices.reserve_and_clear(
-        extracted_sprite_len * quad_vertex_positions.len(),
+        extracted_sprites.len() * quad_vertex_positions.len(),
+        &render_device,
+    );
+    sprite_meta.indices.reserve_and_clear(
+        extracted_sprites.len() * quad_indices.len(),
         &render_device,
     );
-    sprite_meta
-        .indices
-        .reserve_and_clear(extracted_sprite_len * quad_indices.len(), &render_device);
 
-    for (i, mut extracted_sprite) in extracted_sprites.iter_mut().enumerate() {
+    let mut batches: Vec<SpriteBatch> = Vec::new();
+
+    for (i, (_entity, extracted_sprite)) in extracted_sprites.iter().enumerate() {
         let sprite_rect = extracted_sprite.rect;
 
         // Specify the corners of the sprite
@@ -289,8 +548,8 @@ pub fn prepare_sprites(
         let bottom_right = sprite_rect.max;
 
         let atlas_positions: [Vec2; 4] = [bottom_left, top_left, top_right, bottom_right];
+        let color = extracted_sprite.color.as_linear_rgba_f32();
 
-        extracted_sprite.vertex_index = i;
         for (index, vertex_position) in quad_vertex_positions.iter().enumerate() {
             let mut final_position =
                 Vec3::from(*vertex_position) * extracted_sprite.rect.size().extend(1.0);
@@ -300,23 +559,44 @@ pub fn prepare_sprites(
                 uv: (atlas_positions[index]
                     / extracted_sprite.atlas_size.unwrap_or(sprite_rect.max))
                 .into(),
+                color,
             });
         }
 
+        let index_start = (i * quad_indices.len()) as u32;
         for index in quad_indices.iter() {
             sprite_meta
                 .indices
                 .push((i * quad_vertex_positions.len()) as u32 + *index);
         }
+        let index_end = index_start + quad_indices.len() as u32;
+
+        match batches.last_mut() {
+            Some(batch)
+                if batch.handle == extracted_sprite.handle
+                    && batch.blend_mode == extracted_sprite.blend_mode
+                    && batch.image_sampling == extracted_sprite.image_sampling =>
+            {
+                batch.range.end = index_end;
+            }
+            _ => batches.push(SpriteBatch {
+                range: index_start..index_end,
+                handle: extracted_sprite.handle.clone_weak(),
+                blend_mode: extracted_sprite.blend_mode,
+                image_sampling: extracted_sprite.image_sampling,
+            }),
+        }
     }
 
     sprite_meta.vertices.write_buffer(&render_queue);
     sprite_meta.indices.write_buffer(&render_queue);
+
+    commands.spawn_batch(batches.into_iter().map(|batch| (batch,)));
 }
 
 #[derive(Default)]
 pub struct ImageBindGroups {
-    values: HashMap<Handle<Image>, BindGroup>,
+    values: HashMap<(Handle<Image>, ImageSampling), BindGroup>,
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -325,10 +605,11 @@ pub fn queue_sprites(
     render_device: Res<RenderDevice>,
     mut sprite_meta: ResMut<SpriteMeta>,
     view_uniforms: Res<ViewUniforms>,
-    sprite_pipeline: Res<SpritePipeline>,
+    mut sprite_pipeline: ResMut<SpritePipeline>,
+    mut pipeline_cache: ResMut<RenderPipelineCache>,
     mut image_bind_groups: ResMut<ImageBindGroups>,
     gpu_images: Res<RenderAssets<Image>>,
-    mut extracted_sprites: Query<(Entity, &ExtractedSprite)>,
+    sprite_batches: Query<(Entity, &SpriteBatch)>,
     mut views: Query<&mut RenderPhase<Transparent2d>>,
 ) {
     if let Some(view_binding) = view_uniforms.uniforms.binding() {
@@ -342,12 +623,12 @@ pub fn queue_sprites(
         }));
         let draw_sprite_function = draw_functions.read().get_id::<DrawSprite>().unwrap();
         for mut transparent_phase in views.iter_mut() {
-            for (entity, sprite) in extracted_sprites.iter_mut() {
+            for (entity, batch) in sprite_batches.iter() {
                 image_bind_groups
                     .values
-                    .entry(sprite.handle.clone_weak())
+                    .entry((batch.handle.clone_weak(), batch.image_sampling))
                     .or_insert_with(|| {
-                        let gpu_image = gpu_images.get(&sprite.handle).unwrap();
+                        let gpu_image = gpu_images.get(&batch.handle).unwrap();
                         render_device.create_bind_group(&BindGroupDescriptor {
                             entries: &[
                                 BindGroupEntry {
@@ -356,18 +637,26 @@ pub fn queue_sprites(
                                 },
                                 BindGroupEntry {
                                     binding: 1,
-                                    resource: BindingResource::Sampler(&gpu_image.sampler),
+                                    resource: BindingResource::Sampler(
+                                        sprite_pipeline.sampler(batch.image_sampling),
+                                    ),
                                 },
                             ],
                             label: Some("sprite_material_bind_group"),
                             layout: &sprite_pipeline.material_layout,
                         })
                     });
+                let pipeline = sprite_pipeline.specialize(
+                    &mut pipeline_cache,
+                    SpriteKey {
+                        blend_mode: batch.blend_mode,
+                    },
+                );
                 transparent_phase.add(Transparent2d {
                     draw_function: draw_sprite_function,
-                    pipeline: sprite_pipeline.pipeline,
+                    pipeline,
                     entity,
-                    sort_key: sprite.handle.clone_weak(),
+                    sort_key: batch.handle.clone_weak(),
                 });
             }
         }
@@ -380,7 +669,7 @@ pub struct DrawSprite {
         SRes<ImageBindGroups>,
         SRes<RenderPipelineCache>,
         SQuery<Read<ViewUniformOffset>>,
-        SQuery<Read<ExtractedSprite>>,
+        SQuery<Read<SpriteBatch>>,
     )>,
 }
 
@@ -400,12 +689,11 @@ impl Draw<Transparent2d> for DrawSprite {
         view: Entity,
         item: &Transparent2d,
     ) {
-        const INDICES: usize = 6;
-        let (sprite_meta, image_bind_groups, pipelines, views, sprites) = self.params.get(world);
+        let (sprite_meta, image_bind_groups, pipelines, views, batches) = self.params.get(world);
         let view_uniform = views.get(view).unwrap();
         let sprite_meta = sprite_meta.into_inner();
         let image_bind_groups = image_bind_groups.into_inner();
-        let extracted_sprite = sprites.get(item.entity).unwrap();
+        let batch = batches.get(item.entity).unwrap();
         if let Some(pipeline) = pipelines.into_inner().get(item.pipeline) {
             pass.set_render_pipeline(pipeline);
             pass.set_vertex_buffer(0, sprite_meta.vertices.buffer().unwrap().slice(..));
@@ -423,17 +711,12 @@ impl Draw<Transparent2d> for DrawSprite {
                 1,
                 image_bind_groups
                     .values
-                    .get(&extracted_sprite.handle)
+                    .get(&(batch.handle.clone_weak(), batch.image_sampling))
                     .unwrap(),
                 &[],
             );
 
-            pass.draw_indexed(
-                (extracted_sprite.vertex_index * INDICES) as u32
-                    ..(extracted_sprite.vertex_index * INDICES + INDICES) as u32,
-                0,
-                0..1,
-            );
+            pass.draw_indexed(batch.range.clone(), 0, 0..1);
         }
     }
 }
\ No newline at end of file
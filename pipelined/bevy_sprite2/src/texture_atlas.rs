@@ -0,0 +1,31 @@
+use crate::{rect::Rect, render::BlendMode};
+use bevy_asset::Handle;
+use bevy_ecs::component::Component;
+use bevy_math::Vec2;
+use bevy_render2::{color::Color, texture::Image};
+
+/// A texture atlas ("sprite sheet") containing many smaller textures packed into one.
+pub struct TextureAtlas {
+    pub texture: Handle<Image>,
+    pub size: Vec2,
+    pub textures: Vec<Rect>,
+}
+
+/// A component that selects which rect of a `TextureAtlas` a sprite entity draws.
+#[derive(Debug, Clone, Copy, Component)]
+pub struct TextureAtlasSprite {
+    pub index: u32,
+    pub color: Color,
+    /// How this sprite's texels are combined with what's already in the render target.
+    pub blend_mode: BlendMode,
+}
+
+impl Default for TextureAtlasSprite {
+    fn default() -> Self {
+        Self {
+            index: 0,
+            color: Color::WHITE,
+            blend_mode: Default::default(),
+        }
+    }
+}